@@ -1,9 +1,55 @@
 use serde::Serialize;
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize)]
 pub struct ServerStatus {
-    pub workers: Vec<WorkerScore>,
-    // TODO Other info
+    pub summary: Option<ServerSummary>,
+    pub workers: Workers,
+}
+
+/// Per-worker data, in whichever shape the input format was able to supply.
+///
+/// The full HTML scoreboard table carries one `WorkerScore` per worker, but
+/// the compact `?auto` format only exposes the single-character status code
+/// for each slot, so it is represented separately instead of faking the
+/// missing `WorkerScore` fields.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum Workers {
+    Detailed(Vec<WorkerScore>),
+    Scoreboard(Vec<WorkerStatus>),
+}
+
+/// Server-wide scalar metrics.
+///
+/// All fields are optional since availability depends on which input format
+/// was parsed, Apache's build flags (e.g. `HAVE_TIMES`) and its version. The
+/// HTML-only fields (version/MPM, per-category CPU seconds, load averages)
+/// are only ever populated from the scoreboard page, since the `?auto`
+/// format doesn't carry them.
+#[derive(Debug, Default, Serialize)]
+pub struct ServerSummary {
+    pub server_version: Option<String>,
+    pub server_mpm: Option<String>,
+
+    pub total_accesses: Option<u64>,
+    pub total_kbytes: Option<u64>,
+    pub cpu_load: Option<f32>,
+    pub uptime_s: Option<u64>,
+    pub req_per_sec: Option<f32>,
+    pub bytes_per_sec: Option<f32>,
+    pub bytes_per_req: Option<f32>,
+    pub busy_workers: Option<u32>,
+    pub idle_workers: Option<u32>,
+
+    /// 1/5/15-minute load averages, from the HTML page's "Server load" line.
+    pub load_average: Option<[f32; 3]>,
+
+    /// CPU seconds consumed, broken down by category, from the HTML page's
+    /// "CPU Usage" line (`u`/`s`/`cu`/`cs` in mod_status's own output).
+    pub cpu_user_s: Option<f32>,
+    pub cpu_system_s: Option<f32>,
+    pub cpu_children_user_s: Option<f32>,
+    pub cpu_children_system_s: Option<f32>,
 }
 
 /// Analog to the 'worker_score' struct:
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FetchError {
+    #[error("request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("server responded with status {0}")]
+    UnexpectedStatus(reqwest::StatusCode),
+}
+
+/// Authentication to attach to the upstream request.
+pub enum Auth {
+    Basic { username: String, password: Option<String> },
+    Bearer(String),
+}
+
+pub struct FetchOptions {
+    pub auth: Option<Auth>,
+    pub timeout: Duration,
+    pub accept_invalid_certs: bool,
+    pub retries: u32,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            auth: None,
+            timeout: Duration::from_secs(10),
+            accept_invalid_certs: false,
+            retries: 0,
+        }
+    }
+}
+
+/// Fetch a mod_status page over HTTP/HTTPS.
+///
+/// Retries `options.retries` times on failure (request error or non-2xx
+/// status) before giving up. On success the response body is returned as-is,
+/// ready to be handed to `parser::parse_auto` or HTML-parsed as before.
+pub fn fetch(url: &str, options: &FetchOptions) -> Result<String, FetchError> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(options.timeout)
+        .danger_accept_invalid_certs(options.accept_invalid_certs)
+        .build()?;
+
+    let mut last_err = None;
+    for _ in 0..=options.retries {
+        match fetch_once(&client, url, options) {
+            Ok(body) => return Ok(body),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    Err(last_err.expect("loop runs at least once"))
+}
+
+fn fetch_once(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    options: &FetchOptions,
+) -> Result<String, FetchError> {
+    let request = client.get(url);
+    let request = match &options.auth {
+        Some(Auth::Basic { username, password }) => request.basic_auth(username, password.as_deref()),
+        Some(Auth::Bearer(token)) => request.bearer_auth(token),
+        None => request,
+    };
+
+    let response = request.send()?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(FetchError::UnexpectedStatus(status));
+    }
+
+    Ok(response.text()?)
+}
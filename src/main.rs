@@ -1,31 +1,170 @@
 use std::io::{self, Read};
+use std::time::Duration;
 
-use data::ServerStatus;
+use clap::{Args, Parser, Subcommand};
+use data::{ServerStatus, Workers};
 use select::document::Document;
 
 mod parser;
 mod data;
+mod fetch;
+mod metrics;
+mod serve;
+
+/// Parse Apache mod_status output (HTML scoreboard or `?auto`) into JSON.
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Cli {
+    /// URL to fetch mod_status from, e.g. `https://host/server-status?auto`.
+    /// If omitted, input is read from stdin instead. Unused by `serve`.
+    url: Option<String>,
+
+    #[command(flatten)]
+    fetch_args: FetchArgs,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run as a long-lived exporter, serving Prometheus metrics at `/metrics`.
+    Serve(ServeArgs),
+}
+
+#[derive(Args)]
+struct ServeArgs {
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:9117")]
+    listen: String,
+
+    /// Upstream mod_status URL to scrape, e.g. `http://host/server-status?auto`.
+    #[arg(long)]
+    upstream_url: String,
+
+    /// How long, in seconds, the last successful parse is reused; scrapes
+    /// within this window don't hit the upstream. Failed scrapes aren't
+    /// cached, so the next request after one fetches the upstream again.
+    #[arg(long, default_value_t = 5)]
+    scrape_interval_secs: u64,
+
+    #[command(flatten)]
+    fetch_args: FetchArgs,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    /// HTTP Basic auth credentials, as `user:pass` or `user` (no password).
+    #[arg(long, value_name = "USER[:PASS]")]
+    basic_auth: Option<String>,
+
+    /// HTTP Bearer auth token.
+    #[arg(long, value_name = "TOKEN")]
+    bearer_token: Option<String>,
+
+    /// Timeout for the upstream request, in seconds.
+    #[arg(long, default_value_t = 10)]
+    timeout_secs: u64,
+
+    /// Number of times to retry a failed request.
+    #[arg(long, default_value_t = 0)]
+    retries: u32,
+
+    /// Accept self-signed/invalid TLS certificates.
+    #[arg(long)]
+    insecure: bool,
+}
+
+impl FetchArgs {
+    fn auth(&self) -> Option<fetch::Auth> {
+        if let Some(token) = &self.bearer_token {
+            return Some(fetch::Auth::Bearer(token.clone()));
+        }
+        self.basic_auth.as_ref().map(|creds| match creds.split_once(':') {
+            Some((user, pass)) => fetch::Auth::Basic {
+                username: user.to_string(),
+                password: Some(pass.to_string()),
+            },
+            None => fetch::Auth::Basic {
+                username: creds.clone(),
+                password: None,
+            },
+        })
+    }
+
+    fn fetch_options(&self) -> fetch::FetchOptions {
+        fetch::FetchOptions {
+            auth: self.auth(),
+            timeout: Duration::from_secs(self.timeout_secs),
+            accept_invalid_certs: self.insecure,
+            retries: self.retries,
+        }
+    }
+}
 
 fn main() {
-    let document: Document = {
-        let mut buffer = String::new();
-        io::stdin().read_to_string(&mut buffer).unwrap();
-        Document::from(buffer.as_str())
-    };
+    let cli = Cli::parse();
 
-    let workers = match parser::parse_worker_scores(&document) {
-        Ok(workers) => workers,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+    match cli.command {
+        Some(Command::Serve(args)) => {
+            serve::run(serve::ServeConfig {
+                listen: args.listen,
+                upstream_url: args.upstream_url,
+                scrape_interval: Duration::from_secs(args.scrape_interval_secs),
+                fetch_options: args.fetch_args.fetch_options(),
+            });
+        }
+        None => run_once(&cli),
+    }
+}
+
+/// Fetch (or read from stdin) and parse a single mod_status page, printing
+/// it as JSON.
+fn run_once(cli: &Cli) {
+    let input = match &cli.url {
+        Some(url) => match fetch::fetch(url, &cli.fetch_args.fetch_options()) {
+            Ok(body) => body,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => {
+            let mut buffer = String::new();
+            io::stdin().read_to_string(&mut buffer).unwrap();
+            buffer
         }
     };
-    let server_status = ServerStatus{
-        workers,
+
+    // mod_status serves an HTML scoreboard table by default, and a
+    // line-oriented `Key: Value` format when queried with `?auto`.
+    let server_status = if input.trim_start().starts_with('<') {
+        let document = Document::from(input.as_str());
+        match parser::parse_worker_scores(&document, &parser::ParseOptions::default()) {
+            Ok((workers, errors)) => {
+                for (row_index, e) in &errors {
+                    eprintln!("Warning: row {}: {}", row_index, e);
+                }
+                ServerStatus {
+                    summary: Some(parser::parse_server_summary(&document)),
+                    workers: Workers::Detailed(workers),
+                }
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        match parser::parse_auto(&input) {
+            Ok(server_status) => server_status,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
     };
 
-    {
-        let json = serde_json::to_string_pretty(&server_status).unwrap();
-        println!("{}", json);
-    }
+    let json = serde_json::to_string_pretty(&server_status).unwrap();
+    println!("{}", json);
 }
@@ -0,0 +1,61 @@
+use crate::data::{ServerStatus, WorkerStatus, Workers};
+
+/// Render a `ServerStatus` as Prometheus text exposition format.
+pub fn render(status: &ServerStatus, scrape_failures: u64) -> String {
+    let mut out = String::new();
+
+    if let Some(summary) = &status.summary {
+        push_gauge(&mut out, "apache_total_accesses", summary.total_accesses.map(|v| v as f64));
+        push_gauge(&mut out, "apache_total_kbytes", summary.total_kbytes.map(|v| v as f64));
+        push_gauge(&mut out, "apache_cpu_load", summary.cpu_load.map(|v| v as f64));
+        push_gauge(&mut out, "apache_uptime_seconds", summary.uptime_s.map(|v| v as f64));
+        push_gauge(&mut out, "apache_requests_per_second", summary.req_per_sec.map(|v| v as f64));
+        push_gauge(&mut out, "apache_bytes_per_second", summary.bytes_per_sec.map(|v| v as f64));
+        push_gauge(&mut out, "apache_bytes_per_request", summary.bytes_per_req.map(|v| v as f64));
+        push_gauge(&mut out, "apache_busy_workers", summary.busy_workers.map(|v| v as f64));
+        push_gauge(&mut out, "apache_idle_workers", summary.idle_workers.map(|v| v as f64));
+    }
+
+    for (label, count) in worker_status_counts(&status.workers) {
+        out.push_str(&format!("apache_workers{{status=\"{}\"}} {}\n", label, count));
+    }
+
+    out.push_str(&format!(
+        "apache_mod_status_parser_scrape_failures_total {}\n",
+        scrape_failures
+    ));
+
+    out
+}
+
+fn push_gauge(out: &mut String, name: &str, value: Option<f64>) {
+    if let Some(value) = value {
+        out.push_str(&format!("{} {}\n", name, value));
+    }
+}
+
+const STATUS_LABELS: &[(&str, WorkerStatus)] = &[
+    ("dead", WorkerStatus::Dead),
+    ("starting", WorkerStatus::Starting),
+    ("ready", WorkerStatus::Ready),
+    ("busy_read", WorkerStatus::BusyRead),
+    ("busy_write", WorkerStatus::BusyWrite),
+    ("busy_keepalive", WorkerStatus::BusyKeepAlive),
+    ("busy_log", WorkerStatus::BusyLog),
+    ("busy_dns", WorkerStatus::BusyDns),
+    ("closing", WorkerStatus::Closing),
+    ("graceful", WorkerStatus::Graceful),
+    ("idle_kill", WorkerStatus::IdleKill),
+];
+
+fn worker_status_counts(workers: &Workers) -> Vec<(&'static str, u32)> {
+    let statuses: Vec<WorkerStatus> = match workers {
+        Workers::Detailed(scores) => scores.iter().map(|score| score.status.clone()).collect(),
+        Workers::Scoreboard(statuses) => statuses.clone(),
+    };
+
+    STATUS_LABELS
+        .iter()
+        .map(|(label, status)| (*label, statuses.iter().filter(|s| *s == status).count() as u32))
+        .collect()
+}
@@ -1,7 +1,11 @@
-use select::{predicate::{Descendant, Name, And, Attr}, document::Document};
+use select::{predicate::{Descendant, Name, And, Attr, Or}, document::Document};
 use thiserror::Error;
 
-use crate::data::{WorkerScore, WorkerStatus, AccessCounts};
+use crate::data::{ServerStatus, ServerSummary, WorkerScore, WorkerStatus, Workers, AccessCounts};
+
+/// The scoreboard table is the only `<table border="0">` on the mod_status
+/// page; everything else (version/uptime/totals) lives before it.
+const TABLE_PREDICATE: And<Name<&str>, Attr<&str, &str>> = And(Name("table"), Attr("border", "0"));
 
 #[derive(Debug, Error)]
 pub enum WorkerScoreParseError {
@@ -31,35 +35,50 @@ pub enum WorkerScoreParseError {
     ParseIntError(#[from] std::num::ParseIntError),
 }
 
+/// Controls how `parse_worker_scores` handles a malformed row.
+#[derive(Debug, Default)]
+pub struct ParseOptions {
+    /// Return `Err` on the first malformed row instead of skipping it and
+    /// continuing (the default).
+    pub strict: bool,
+}
+
+/// Successfully parsed rows, alongside the 0-based row index (the header row
+/// excluded) and error of any row that failed to parse.
+pub type ParseResult = (Vec<WorkerScore>, Vec<(usize, WorkerScoreParseError)>);
+
 /// Find the table with worker scores and convert it to a vector of `WorkerScore`s.
-pub fn parse_worker_scores(document: &Document) -> Result<Vec<WorkerScore>, WorkerScoreParseError> {
+///
+/// Rows that fail to parse are skipped and reported alongside their 0-based
+/// row index (the header row excluded) in the second element of the
+/// returned tuple, unless `options.strict` is set, in which case the first
+/// bad row is returned as an `Err` instead.
+pub fn parse_worker_scores(
+    document: &Document,
+    options: &ParseOptions,
+) -> Result<ParseResult, WorkerScoreParseError> {
     const TR_PREDICATE:
         Descendant<And<Name<&str>, Attr<&str, &str>>, Name<&str>> =
-        Descendant(And(Name("table"), Attr("border", "0")), Name("tr"));
+        Descendant(TABLE_PREDICATE, Name("tr"));
 
     let mut scores: Vec<WorkerScore> = Vec::with_capacity(2^8);
-    
+    let mut errors: Vec<(usize, WorkerScoreParseError)> = Vec::new();
+
     for (i, row) in document.find(TR_PREDICATE).enumerate() {
         match i {
             0 => {
                 // Don't continue if the headers are not valid.
                 let _ = validate_headers(&row)?;
             },
-            _ => {
-                match parse_row(&row) {
-                    Ok(score) => scores.push(score),
-                    Err(e) => {
-                        eprintln!("Error: {}", e);
-                        println!("Row: {}", row.html());
-                        std::process::exit(1);
-                    }
-                }
-                // scores.push(parse_row(&row)?);
-            }
+            _ => match parse_row(&row) {
+                Ok(score) => scores.push(score),
+                Err(e) if options.strict => return Err(e),
+                Err(e) => errors.push((i - 1, e)),
+            },
         }
     }
 
-    Ok(scores)
+    Ok((scores, errors))
 }
 
 /// Validate that the headers are right
@@ -146,7 +165,7 @@ fn parse_pid(s: &str) -> Result<Option<i32>, WorkerScoreParseError> {
 }
 
 /// Parse mod_status "M" column
-/// 
+///
 /// See:
 /// - https://github.com/apache/httpd/blob/2.4.56/modules/generators/mod_status.c#L865
 fn parse_worker_status(s: &str) -> Result<WorkerStatus, WorkerScoreParseError> {
@@ -156,20 +175,32 @@ fn parse_worker_status(s: &str) -> Result<WorkerStatus, WorkerScoreParseError> {
 
     let code = s.chars().nth(0).unwrap();
 
-    match code {
-        '_' => Ok(WorkerStatus::Ready),
-        'S' => Ok(WorkerStatus::Starting),
-        'R' => Ok(WorkerStatus::BusyRead),
-        'W' => Ok(WorkerStatus::BusyWrite),
-        'K' => Ok(WorkerStatus::BusyKeepAlive),
-        'L' => Ok(WorkerStatus::BusyLog),
-        'D' => Ok(WorkerStatus::BusyDns),
-        'C' => Ok(WorkerStatus::Closing),
-        '.' => Ok(WorkerStatus::Dead),
-        'G' => Ok(WorkerStatus::Graceful),
-        'I' => Ok(WorkerStatus::IdleKill),
-        _ => Err(WorkerScoreParseError::InvalidStatusCode(code)),
-    }
+    worker_status_from_code(code).ok_or(WorkerScoreParseError::InvalidStatusCode(code))
+}
+
+/// Map a single scoreboard status character to a `WorkerStatus`.
+///
+/// Shared between the HTML "M" column (`parse_worker_status`) and the
+/// `?auto` `Scoreboard` line (`parse_auto`), since both encode the same
+/// table.
+///
+/// See:
+/// - https://github.com/apache/httpd/blob/2.4.56/modules/generators/mod_status.c#L865
+fn worker_status_from_code(code: char) -> Option<WorkerStatus> {
+    Some(match code {
+        '_' => WorkerStatus::Ready,
+        'S' => WorkerStatus::Starting,
+        'R' => WorkerStatus::BusyRead,
+        'W' => WorkerStatus::BusyWrite,
+        'K' => WorkerStatus::BusyKeepAlive,
+        'L' => WorkerStatus::BusyLog,
+        'D' => WorkerStatus::BusyDns,
+        'C' => WorkerStatus::Closing,
+        '.' => WorkerStatus::Dead,
+        'G' => WorkerStatus::Graceful,
+        'I' => WorkerStatus::IdleKill,
+        _ => return None,
+    })
 }
 
 /// Parse mod_status "Acc" column
@@ -186,3 +217,449 @@ fn parse_acc (s: &str) -> Result<AccessCounts, WorkerScoreParseError> {
 
     Ok(AccessCounts { connection, child, slot })
 }
+
+#[derive(Debug, Error)]
+pub enum AutoParseError {
+    #[error("invalid scoreboard status code `{0}`")]
+    InvalidStatusCode(char),
+
+    #[error(transparent)]
+    ParseFloatError(#[from] std::num::ParseFloatError),
+    #[error(transparent)]
+    ParseIntError(#[from] std::num::ParseIntError),
+}
+
+/// Parse the compact `?auto` format mod_status serves for monitoring tools.
+///
+/// Unlike the HTML scoreboard table, this is a line-oriented `Key: Value`
+/// format, e.g.:
+///
+/// ```text
+/// Total Accesses: 123
+/// Total kBytes: 456
+/// CPULoad: .0234
+/// Uptime: 1000
+/// ReqPerSec: .12
+/// BytesPerSec: .12
+/// BytesPerReq: .12
+/// BusyWorkers: 2
+/// IdleWorkers: 8
+/// Scoreboard: __W_K...G.
+/// ```
+///
+/// `pid`/access-count/byte-count fields aren't present per-worker in this
+/// format, so only the `Scoreboard` line's per-character status is kept.
+/// Unrecognized keys are ignored so the parser tolerates fields from newer
+/// Apache versions.
+pub fn parse_auto(input: &str) -> Result<ServerStatus, AutoParseError> {
+    let mut summary = ServerSummary::default();
+    let mut workers = Vec::new();
+
+    for line in input.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        match key.trim() {
+            "Total Accesses" => summary.total_accesses = Some(value.parse()?),
+            "Total kBytes" => summary.total_kbytes = Some(value.parse()?),
+            "CPULoad" => summary.cpu_load = Some(value.parse()?),
+            "Uptime" => summary.uptime_s = Some(value.parse()?),
+            "ReqPerSec" => summary.req_per_sec = Some(value.parse()?),
+            "BytesPerSec" => summary.bytes_per_sec = Some(value.parse()?),
+            "BytesPerReq" => summary.bytes_per_req = Some(value.parse()?),
+            "BusyWorkers" => summary.busy_workers = Some(value.parse()?),
+            "IdleWorkers" => summary.idle_workers = Some(value.parse()?),
+            "Scoreboard" => workers = parse_scoreboard(value)?,
+            _ => (),
+        }
+    }
+
+    Ok(ServerStatus {
+        summary: Some(summary),
+        workers: Workers::Scoreboard(workers),
+    })
+}
+
+/// Parse the `Scoreboard` line of the `?auto` format into one `WorkerStatus`
+/// per character, through the same table as `parse_worker_status`.
+fn parse_scoreboard(s: &str) -> Result<Vec<WorkerStatus>, AutoParseError> {
+    s.trim()
+        .chars()
+        .map(|code| worker_status_from_code(code).ok_or(AutoParseError::InvalidStatusCode(code)))
+        .collect()
+}
+
+/// Parse the server-level summary section that precedes the scoreboard
+/// table on the HTML mod_status page (the `<dl>`/`<p>` blocks covering
+/// server version, uptime, totals and current throughput).
+///
+/// Each summary line mod_status emits ends up as its own `<dt>` (or, in a
+/// couple of older/stripped-down builds, a top-level `<p>`), so rather than
+/// parsing the section's markup directly, every `dt`/`p` node found before
+/// the scoreboard table is matched against the line formats below; anything
+/// unrecognized, including the whole section if the table can't be found,
+/// is silently left as `None` rather than treated as a parse error, since
+/// every field here is inherently best-effort (it varies by Apache version
+/// and build flags).
+pub fn parse_server_summary(document: &Document) -> ServerSummary {
+    let mut summary = ServerSummary::default();
+
+    let table_index = match document.find(TABLE_PREDICATE).next() {
+        Some(table) => table.index(),
+        None => return summary,
+    };
+
+    for node in document.find(Or(Name("dt"), Name("p"))) {
+        if node.index() >= table_index {
+            break;
+        }
+        apply_summary_line(&mut summary, node.text().trim());
+    }
+
+    summary
+}
+
+fn apply_summary_line(summary: &mut ServerSummary, line: &str) {
+    if let Some(value) = line.strip_prefix("Server Version:") {
+        summary.server_version = Some(value.trim().to_string());
+    } else if let Some(value) = line.strip_prefix("Server MPM:") {
+        summary.server_mpm = Some(value.trim().to_string());
+    } else if let Some(value) = line.strip_prefix("Server uptime:") {
+        summary.uptime_s = parse_uptime(value.trim());
+    } else if let Some(value) = line.strip_prefix("Server load:") {
+        summary.load_average = parse_load_average(value.trim());
+    } else if let Some(value) = line.strip_prefix("CPU Usage:") {
+        apply_cpu_usage(summary, value.trim());
+    } else if line.starts_with("Total accesses:") {
+        apply_totals(summary, line);
+    } else if line.contains("requests/sec") {
+        apply_throughput(summary, line);
+    } else if line.contains("currently being processed") {
+        apply_worker_counts(summary, line);
+    }
+}
+
+/// Parse mod_status's "Server uptime" value, e.g. `13 days 2 hours 39 minutes`.
+fn parse_uptime(s: &str) -> Option<u64> {
+    let tokens: Vec<&str> = s
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let mut total = 0u64;
+    let mut matched_any = false;
+    let mut iter = tokens.iter().peekable();
+
+    while let Some(num_str) = iter.next() {
+        let Ok(n) = num_str.parse::<u64>() else {
+            continue;
+        };
+        let Some(unit) = iter.next() else {
+            break;
+        };
+        let multiplier = match unit.trim_end_matches('s') {
+            "day" => 86_400,
+            "hour" => 3_600,
+            "minute" => 60,
+            "second" => 1,
+            _ => continue,
+        };
+        total += n * multiplier;
+        matched_any = true;
+    }
+
+    matched_any.then_some(total)
+}
+
+/// Parse mod_status's "Server load" value: 1/5/15-minute load averages.
+fn parse_load_average(s: &str) -> Option<[f32; 3]> {
+    let parts: Vec<&str> = s.split_whitespace().collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    Some([parts[0].parse().ok()?, parts[1].parse().ok()?, parts[2].parse().ok()?])
+}
+
+/// Parse mod_status's "CPU Usage" value, e.g. `u.49 s.17 cu0 cs0 - .0001% CPU load`.
+fn apply_cpu_usage(summary: &mut ServerSummary, s: &str) {
+    let (components, load) = match s.split_once('-') {
+        Some((a, b)) => (a, Some(b)),
+        None => (s, None),
+    };
+
+    for token in components.split_whitespace() {
+        if let Some(value) = token.strip_prefix("cu") {
+            summary.cpu_children_user_s = value.parse().ok();
+        } else if let Some(value) = token.strip_prefix("cs") {
+            summary.cpu_children_system_s = value.parse().ok();
+        } else if let Some(value) = token.strip_prefix('u') {
+            summary.cpu_user_s = value.parse().ok();
+        } else if let Some(value) = token.strip_prefix('s') {
+            summary.cpu_system_s = value.parse().ok();
+        }
+    }
+
+    if let Some(percent) = load.and_then(|l| l.trim().strip_suffix("% CPU load")) {
+        summary.cpu_load = percent.trim().parse().ok();
+    }
+}
+
+/// Parse mod_status's combined totals line, e.g.
+/// `Total accesses: 107426 - Total Traffic: 2.1 GB - Total Duration: 12`.
+fn apply_totals(summary: &mut ServerSummary, line: &str) {
+    for part in line.split(" - ") {
+        let part = part.trim();
+        if let Some(value) = part.strip_prefix("Total accesses:") {
+            summary.total_accesses = value.trim().parse().ok();
+        } else if let Some(value) = part.strip_prefix("Total Traffic:") {
+            summary.total_kbytes = parse_size_to_kb(value.trim());
+        }
+    }
+}
+
+/// Parse a `<number> <unit>` size (`B`/`kB`/`MB`/`GB`/`TB`) into kilobytes.
+fn parse_size_to_kb(s: &str) -> Option<u64> {
+    let (number, unit) = s.split_once(' ')?;
+    let value: f64 = number.parse().ok()?;
+    let kb = match unit.to_ascii_uppercase().as_str() {
+        "B" => value / 1024.0,
+        "KB" | "K" => value,
+        "MB" | "M" => value * 1024.0,
+        "GB" | "G" => value * 1024.0 * 1024.0,
+        "TB" | "T" => value * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some(kb.round() as u64)
+}
+
+/// Parse mod_status's current-throughput line, e.g.
+/// `.01 requests/sec - 27 B/second - 2048 B/request`.
+fn apply_throughput(summary: &mut ServerSummary, line: &str) {
+    for part in line.split(" - ") {
+        let part = part.trim();
+        if let Some(value) = part.strip_suffix("requests/sec") {
+            summary.req_per_sec = value.trim().parse().ok();
+        } else if let Some(value) = part.strip_suffix("/second") {
+            summary.bytes_per_sec = parse_rate_to_bytes(value.trim());
+        } else if let Some(value) = part.strip_suffix("/request") {
+            summary.bytes_per_req = parse_rate_to_bytes(value.trim());
+        }
+    }
+}
+
+/// Parse a `<number> <unit>` rate (`B`/`kB`/`MB`/`GB`) into bytes.
+fn parse_rate_to_bytes(s: &str) -> Option<f32> {
+    let (number, unit) = s.split_once(' ')?;
+    let value: f32 = number.parse().ok()?;
+    match unit.to_ascii_uppercase().as_str() {
+        "B" => Some(value),
+        "KB" => Some(value * 1024.0),
+        "MB" => Some(value * 1024.0 * 1024.0),
+        "GB" => Some(value * 1024.0 * 1024.0 * 1024.0),
+        _ => None,
+    }
+}
+
+/// Parse mod_status's worker-count line, e.g.
+/// `2 requests currently being processed, 8 idle workers`.
+fn apply_worker_counts(summary: &mut ServerSummary, line: &str) {
+    let Some((busy, idle)) = line.split_once(',') else {
+        return;
+    };
+
+    if let Some(n) = busy.split_whitespace().next() {
+        summary.busy_workers = n.parse().ok();
+    }
+    if let Some(n) = idle.split_whitespace().next() {
+        summary.idle_workers = n.parse().ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_auto_reads_scalar_fields() {
+        let input = "\
+Total Accesses: 123
+Total kBytes: 456
+CPULoad: .0234
+Uptime: 1000
+ReqPerSec: .12
+BytesPerSec: 34.5
+BytesPerReq: 678.9
+BusyWorkers: 2
+IdleWorkers: 8
+Scoreboard: __W_K...G.
+";
+
+        let status = parse_auto(input).unwrap();
+        let summary = status.summary.unwrap();
+
+        assert_eq!(summary.total_accesses, Some(123));
+        assert_eq!(summary.total_kbytes, Some(456));
+        assert_eq!(summary.cpu_load, Some(0.0234));
+        assert_eq!(summary.uptime_s, Some(1000));
+        assert_eq!(summary.req_per_sec, Some(0.12));
+        assert_eq!(summary.bytes_per_sec, Some(34.5));
+        assert_eq!(summary.bytes_per_req, Some(678.9));
+        assert_eq!(summary.busy_workers, Some(2));
+        assert_eq!(summary.idle_workers, Some(8));
+    }
+
+    #[test]
+    fn parse_auto_reconstructs_scoreboard_statuses() {
+        let input = "Scoreboard: __W_K...G.\n";
+
+        let status = parse_auto(input).unwrap();
+
+        match status.workers {
+            Workers::Scoreboard(statuses) => assert_eq!(
+                statuses,
+                vec![
+                    WorkerStatus::Ready,
+                    WorkerStatus::Ready,
+                    WorkerStatus::BusyWrite,
+                    WorkerStatus::Ready,
+                    WorkerStatus::BusyKeepAlive,
+                    WorkerStatus::Dead,
+                    WorkerStatus::Dead,
+                    WorkerStatus::Dead,
+                    WorkerStatus::Graceful,
+                    WorkerStatus::Dead,
+                ]
+            ),
+            Workers::Detailed(_) => panic!("expected Workers::Scoreboard"),
+        }
+    }
+
+    #[test]
+    fn parse_auto_rejects_invalid_scoreboard_char() {
+        let input = "Scoreboard: __X_\n";
+
+        let err = parse_auto(input).unwrap_err();
+
+        assert!(matches!(err, AutoParseError::InvalidStatusCode('X')));
+    }
+
+    #[test]
+    fn parse_auto_ignores_unknown_fields() {
+        let input = "ConnsTotal: 42\nScoreboard: _\n";
+
+        let status = parse_auto(input).unwrap();
+
+        assert_eq!(status.summary.unwrap().busy_workers, None);
+    }
+
+    #[test]
+    fn parse_server_summary_extracts_fields_from_html() {
+        let html = r#"
+            <html><body>
+            <dl><dt>Server Version: Apache/2.4.41 (Ubuntu)</dt>
+            <dt>Server MPM: event</dt></dl>
+            <dl>
+            <dt>Server uptime:  13 days 2 hours 39 minutes</dt>
+            <dt>Server load: 0.01 0.05 0.05</dt>
+            <dt>Total accesses: 107426 - Total Traffic: 2.1 GB - Total Duration: 12</dt>
+            <dt>CPU Usage: u.49 s.17 cu0 cs0 - .0001% CPU load</dt>
+            <dt>.01 requests/sec - 27 B/second - 2048 B/request</dt>
+            <dt>2 requests currently being processed, 8 idle workers</dt>
+            </dl>
+            <table border="0"><tr><td>Srv</td></tr></table>
+            </body></html>
+        "#;
+
+        let document = Document::from(html);
+        let summary = parse_server_summary(&document);
+
+        assert_eq!(summary.server_version.as_deref(), Some("Apache/2.4.41 (Ubuntu)"));
+        assert_eq!(summary.server_mpm.as_deref(), Some("event"));
+        assert_eq!(summary.uptime_s, Some(13 * 86_400 + 2 * 3_600 + 39 * 60));
+        assert_eq!(summary.load_average, Some([0.01, 0.05, 0.05]));
+        assert_eq!(summary.total_accesses, Some(107_426));
+        assert_eq!(summary.total_kbytes, Some((2.1_f64 * 1024.0 * 1024.0).round() as u64));
+        assert_eq!(summary.cpu_user_s, Some(0.49));
+        assert_eq!(summary.cpu_system_s, Some(0.17));
+        assert_eq!(summary.cpu_children_user_s, Some(0.0));
+        assert_eq!(summary.cpu_children_system_s, Some(0.0));
+        assert_eq!(summary.cpu_load, Some(0.0001));
+        assert_eq!(summary.req_per_sec, Some(0.01));
+        assert_eq!(summary.bytes_per_sec, Some(27.0));
+        assert_eq!(summary.bytes_per_req, Some(2048.0));
+        assert_eq!(summary.busy_workers, Some(2));
+        assert_eq!(summary.idle_workers, Some(8));
+    }
+
+    #[test]
+    fn parse_server_summary_defaults_when_table_missing() {
+        let document = Document::from("<html><body>no scoreboard here</body></html>");
+
+        let summary = parse_server_summary(&document);
+
+        assert_eq!(summary.server_version, None);
+    }
+
+    #[test]
+    fn parse_uptime_handles_pluralized_and_singular_units() {
+        assert_eq!(
+            parse_uptime("13 days 2 hours 39 minutes"),
+            Some(13 * 86_400 + 2 * 3_600 + 39 * 60)
+        );
+        assert_eq!(
+            parse_uptime("1 day, 1 hour, 1 minute, 1 second"),
+            Some(86_400 + 3_600 + 60 + 1)
+        );
+        assert_eq!(parse_uptime("not a duration"), None);
+    }
+
+    #[test]
+    fn parse_load_average_requires_exactly_three_numbers() {
+        assert_eq!(parse_load_average("0.01 0.05 0.05"), Some([0.01, 0.05, 0.05]));
+        assert_eq!(parse_load_average("0.01 0.05"), None);
+    }
+
+    #[test]
+    fn apply_cpu_usage_splits_components_and_load_percent() {
+        let mut summary = ServerSummary::default();
+
+        apply_cpu_usage(&mut summary, "u.49 s.17 cu0 cs0 - .0001% CPU load");
+
+        assert_eq!(summary.cpu_user_s, Some(0.49));
+        assert_eq!(summary.cpu_system_s, Some(0.17));
+        assert_eq!(summary.cpu_children_user_s, Some(0.0));
+        assert_eq!(summary.cpu_children_system_s, Some(0.0));
+        assert_eq!(summary.cpu_load, Some(0.0001));
+    }
+
+    #[test]
+    fn parse_size_to_kb_converts_units() {
+        assert_eq!(parse_size_to_kb("1 MB"), Some(1024));
+        assert_eq!(parse_size_to_kb("1 GB"), Some(1024 * 1024));
+        assert_eq!(parse_size_to_kb("1024 B"), Some(1));
+        assert_eq!(parse_size_to_kb("garbage"), None);
+    }
+
+    #[test]
+    fn apply_throughput_splits_combined_line() {
+        let mut summary = ServerSummary::default();
+
+        apply_throughput(&mut summary, ".01 requests/sec - 27 B/second - 2048 B/request");
+
+        assert_eq!(summary.req_per_sec, Some(0.01));
+        assert_eq!(summary.bytes_per_sec, Some(27.0));
+        assert_eq!(summary.bytes_per_req, Some(2048.0));
+    }
+
+    #[test]
+    fn apply_worker_counts_splits_on_comma() {
+        let mut summary = ServerSummary::default();
+
+        apply_worker_counts(&mut summary, "2 requests currently being processed, 8 idle workers");
+
+        assert_eq!(summary.busy_workers, Some(2));
+        assert_eq!(summary.idle_workers, Some(8));
+    }
+}
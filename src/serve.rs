@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tiny_http::{Response, Server};
+
+use crate::data::ServerStatus;
+use crate::fetch::{self, FetchOptions};
+use crate::metrics;
+use crate::parser;
+
+pub struct ServeConfig {
+    pub listen: String,
+    pub upstream_url: String,
+    pub scrape_interval: Duration,
+    pub fetch_options: FetchOptions,
+}
+
+/// Cache of the last successful scrape, so repeated `/metrics` requests
+/// within `scrape_interval` reuse it instead of re-hitting the upstream.
+struct Cache {
+    last: Mutex<Option<(Instant, ServerStatus)>>,
+    scrape_interval: Duration,
+}
+
+impl Cache {
+    fn render(&self, config: &ServeConfig, failures: &AtomicU64) -> Result<String, String> {
+        {
+            let last = self.last.lock().unwrap();
+            if let Some((fetched_at, status)) = last.as_ref() {
+                if fetched_at.elapsed() < self.scrape_interval {
+                    return Ok(metrics::render(status, failures.load(Ordering::Relaxed)));
+                }
+            }
+        }
+
+        match scrape(&config.upstream_url, &config.fetch_options) {
+            Ok(status) => {
+                let rendered = metrics::render(&status, failures.load(Ordering::Relaxed));
+                *self.last.lock().unwrap() = Some((Instant::now(), status));
+                Ok(rendered)
+            }
+            Err(e) => {
+                failures.fetch_add(1, Ordering::Relaxed);
+                Err(e)
+            }
+        }
+    }
+}
+
+fn scrape(upstream_url: &str, fetch_options: &FetchOptions) -> Result<ServerStatus, String> {
+    let body = fetch::fetch(upstream_url, fetch_options).map_err(|e| e.to_string())?;
+    parser::parse_auto(&body).map_err(|e| e.to_string())
+}
+
+/// Run the exporter daemon: on each `/metrics` request, scrape
+/// `config.upstream_url` (reusing the last successful parse within
+/// `config.scrape_interval`) and respond with the Prometheus exposition
+/// format. Also serves `/healthz` for liveness checks.
+pub fn run(config: ServeConfig) -> ! {
+    let server = Server::http(&config.listen).unwrap_or_else(|e| {
+        eprintln!("Error: failed to listen on {}: {}", config.listen, e);
+        std::process::exit(1);
+    });
+
+    let cache = Cache {
+        last: Mutex::new(None),
+        scrape_interval: config.scrape_interval,
+    };
+    let scrape_failures = AtomicU64::new(0);
+
+    for request in server.incoming_requests() {
+        let response = match request.url() {
+            "/metrics" => match cache.render(&config, &scrape_failures) {
+                Ok(body) => Response::from_string(body),
+                Err(e) => Response::from_string(format!("scrape failed: {}\n", e)).with_status_code(502),
+            },
+            "/healthz" => Response::from_string("ok\n"),
+            _ => Response::from_string("not found\n").with_status_code(404),
+        };
+
+        let _ = request.respond(response);
+    }
+
+    unreachable!("tiny_http::Server::incoming_requests() never returns");
+}